@@ -1,23 +1,26 @@
 use std::marker::PhantomData;
 
-#[allow(unused_imports)] // reason: used in docs
-use bevy::{app::FixedMain, ecs::schedule::ScheduleLabel, prelude::*};
-
-#[cfg(feature = "derive")]
-pub use bevy_previous_derive::DefaultSchedule;
+use bevy::{
+    ecs::schedule::{Interned, ScheduleLabel},
+    prelude::*,
+};
 
 /// A component that represents the previous value of another component `T`.
 /// To enable previous-value-tracking for a component use [`PreviousPlugin`].
-/// The parameter `S` must be the same as the one specified in [`PreviousPlugin`],
-/// or be ommited, like with [`PreviousPlugin`].
+///
+/// The `Tag` parameter is a free marker type you can use to disambiguate
+/// between several [`PreviousPlugin<T>`]s tracking the same `T` (for example
+/// with two different schedules). Most users can leave it at its default of
+/// `()`; if you do provide one, it must match the `Tag` used in the
+/// corresponding [`PreviousPlugin`].
 ///
 /// You don't have to manually add [`Previous`] to your entity.
-/// This is done automatically in the specified schedule `S`.
+/// This is done automatically in the schedule given to [`PreviousPlugin`].
 ///
 /// Also note that queries like `Query<(&T, &Previous<T>)>` won't match entities
-/// that were just created, as the may not have [`Previous`] yet.
-///
-/// Like with [`PreviousPlugin`], there is a [`FixedMain`] type alias for it: [`FixedUpdate`].
+/// that were just created, as the may not have [`Previous`] yet. Likewise,
+/// once `T` is removed from an entity, [`Previous<T>`] is removed along with
+/// it by default; see [`PreviousPlugin::keep_last_on_removal`] to opt out.
 ///
 /// # Examples
 ///
@@ -42,29 +45,28 @@ pub use bevy_previous_derive::DefaultSchedule;
 /// }
 /// ```
 ///
-/// With custom schedule:
+/// With a custom schedule and a tag to tell it apart from a [`Previous<Health>`]
+/// that might be tracked elsewhere:
 ///
 /// ```rust
 /// # use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
 /// # use bevy_previous::*;
 /// #
-/// # #[derive(DefaultSchedule, ScheduleLabel, Debug, Clone, Hash, PartialEq, Eq)]
-/// # struct GameLogic;
-///
-/// #[derive(Component, Clone)]
-/// struct Health(pub u32);
-///
-/// #[derive(DefaultSchedule, ScheduleLabel, Debug, Clone, Hash, PartialEq, Eq)]
+/// #[derive(ScheduleLabel, Debug, Clone, Hash, PartialEq, Eq)]
 /// struct AfterGameLogic;
 ///
+/// struct AfterGameLogicTag;
 ///
 /// // create a type alias to reduce boilerplate
-/// type Previous<T> = bevy_previous::Previous<T, AfterGameLogic>;
+/// type Previous<T> = bevy_previous::Previous<T, AfterGameLogicTag>;
+///
+/// #[derive(Component, Clone)]
+/// struct Health(pub u32);
 ///
 /// fn main() {
 ///     App::new()
-///         .add_plugins(PreviousPlugin::<Health, AfterGameLogic>::default())
-///         .add_systems(GameLogic, print_differences)
+///         .add_plugins(PreviousPlugin::<Health, AfterGameLogicTag>::new(AfterGameLogic))
+///         .add_systems(AfterGameLogic, print_differences)
 ///         .run();
 /// }
 ///
@@ -75,64 +77,44 @@ pub use bevy_previous_derive::DefaultSchedule;
 /// }
 /// ```
 #[derive(Component, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Previous<T: Component + Clone, S: ScheduleLabel + Clone = Last>(pub T, PhantomData<S>);
+pub struct Previous<T: Component + Clone, Tag: Send + Sync + 'static = ()>(
+    pub T,
+    PhantomData<Tag>,
+);
 
-impl<T, S> Previous<T, S>
+impl<T, Tag> Previous<T, Tag>
 where
     T: Component + Clone,
-    S: ScheduleLabel + Clone,
+    Tag: Send + Sync + 'static,
 {
     pub fn new(value: T) -> Self {
         Previous(value, PhantomData)
     }
 }
 
-impl<T, S> From<T> for Previous<T, S>
+impl<T, Tag> From<T> for Previous<T, Tag>
 where
     T: Component + Clone,
-    S: ScheduleLabel + Clone,
+    Tag: Send + Sync + 'static,
 {
     fn from(value: T) -> Self {
         Previous::new(value)
     }
 }
 
-/// A type alias for [`Previous<T, FixedLast>`] to be used with [`FixedPreviousPlugin<T>`].
-///
-/// # Examples
-///
-/// ```
-/// # use bevy::prelude::*;
-/// # use bevy_previous::*;
-///
-/// #[derive(Component, Clone)]
-/// struct Health(pub u32);
-///
-/// fn main() {
-///     App::new()
-///         .add_plugins(FixedPreviousPlugin::<Health>::default())
-///         .add_systems(Update, print_differences)
-///         .run();
-/// }
-///
-/// fn print_differences(query: Query<(&Health, &FixedPrevious<Health>), Changed<Health>>) {
-///     for (health, previous_health) in &query {
-///         println!("Health reduced by {}", previous_health.0.0 - health.0);
-///     }
-/// }
-/// ```
-pub type FixedPrevious<T> = Previous<T, FixedLast>;
-
 /// A Plugin to activate the [`Previous`] component for a given component `T`.
-/// The parameter `S` defines the schedule where [`Previous<T>`] components are
-/// set back to the value of `T`. This should be after all of your game logic,
-/// so it is set to [`Last`] by default. For [`FixedLast`], the type alias [`FixedPreviousPlugin`]
-/// is provided.
 ///
-/// If the schedule implements [`DefaultSchedule`] (which all standard schedules do),
-/// you can use `PreviousPlugin::<T, S>::default()` (`S` may be omitted, defaults to [`Last`]).
-/// Otherwise, you will either have to implement [`DefaultSchedule`] for your schedule,
-/// or provide a schedule with `PreviousPlugin::<T, S>::new(schedule)`.
+/// The schedule where [`Previous<T>`] is set back to the value of `T` is
+/// given as an [`Interned<dyn ScheduleLabel>`](Interned), obtained at
+/// construction time via [`ScheduleLabel::intern`]. This means
+/// `PreviousPlugin::<T>::new(schedule)` accepts *any* schedule label,
+/// including ones from third-party crates, with no extra trait impls
+/// required. [`Default`] is provided separately and always copies back in
+/// [`Last`], which covers the common case.
+///
+/// The `Tag` parameter is forwarded to [`Previous<T, Tag>`] and only needs
+/// to be set if you're tracking the same `T` with more than one
+/// [`PreviousPlugin`].
 ///
 /// # Examples
 ///
@@ -157,29 +139,8 @@ pub type FixedPrevious<T> = Previous<T, FixedLast>;
 /// }
 /// ```
 ///
-/// Custom schedule:
-///
-/// ```
-/// # use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
-/// # use bevy_previous::*;
-/// #
-/// # #[derive(DefaultSchedule, ScheduleLabel, Debug, Clone, Hash, PartialEq, Eq)]
-/// # struct GameLogic;
-///
-/// #[derive(Component, Clone)]
-/// struct Health(pub u32);
-///
-/// #[derive(DefaultSchedule, ScheduleLabel, Debug, Clone, Hash, PartialEq, Eq)]
-/// struct AfterGameLogic;
-///
-/// // create a type alias to reduce boilerplate
-/// type Previous<T> = bevy_previous::Previous<T, AfterGameLogic>;
-///
-/// App::new()
-///     .add_plugins(PreviousPlugin::<Health, AfterGameLogic>::default());
-/// ```
-///
-/// Or:
+/// Custom schedule, including third-party ones that don't implement any
+/// special trait for this crate:
 ///
 /// ```
 /// # use bevy::prelude::*;
@@ -190,113 +151,174 @@ pub type FixedPrevious<T> = Previous<T, FixedLast>;
 /// #   pub struct Schedule;
 /// # }
 ///
-/// // doesn't impl DefaultSchedule
 /// use other_lib::Schedule;
 ///
 /// #[derive(Component, Clone)]
 /// struct Health(pub u32);
 ///
-/// // create a type alias to reduce boilerplate
-/// type Previous<T> = bevy_previous::Previous<T, Schedule>;
-///
 /// App::new()
-///     .add_plugins(PreviousPlugin::<Health, Schedule>::new(Schedule))
+///     .add_plugins(PreviousPlugin::<Health>::new(Schedule))
 ///     .run();
 /// ```
 #[derive(Debug, Clone)]
-pub struct PreviousPlugin<T: Component + Clone, S: ScheduleLabel + Clone = Last> {
-    schedule: S,
+pub struct PreviousPlugin<T: Component + Clone, Tag: Send + Sync + 'static = ()> {
+    schedule_label: Interned<dyn ScheduleLabel>,
+    keep_last_on_removal: bool,
+    every: u32,
     _t: PhantomData<T>,
+    _tag: PhantomData<Tag>,
 }
 
-/// A type alias for [`PreviousPlugin<T, FixedLast>`] to be used with [`FixedPrevious<T>`].
-///
-/// *See [PreviousPlugin] for more info*
-pub type FixedPreviousPlugin<T> = PreviousPlugin<T, FixedLast>;
-
-impl<T, S> Plugin for PreviousPlugin<T, S>
+impl<T, Tag> Plugin for PreviousPlugin<T, Tag>
 where
     T: Component + Clone,
-    S: ScheduleLabel + Clone,
+    Tag: Send + Sync + 'static,
 {
     fn build(&self, app: &mut App) {
-        app.add_systems(self.schedule.clone(), update::<T>);
+        let every = self.every;
+        app.add_systems(
+            self.schedule_label,
+            (
+                init::<T, Tag>,
+                sample::<T, Tag>.run_if(move |mut tick: Local<u32>| {
+                    let should_run = *tick == 0;
+                    *tick = (*tick + 1) % every;
+                    should_run
+                }),
+            )
+                .in_set(PreviousSet),
+        );
+        if !self.keep_last_on_removal {
+            app.add_systems(
+                self.schedule_label,
+                remove_stale::<T, Tag>.after(PreviousSet),
+            );
+        }
     }
 }
 
-type UpdateFilter<T> = Or<(Without<Previous<T>>, Changed<T>)>;
-fn update<T: Component + Clone>(
+/// The [`SystemSet`] that the system copying `T` into [`Previous<T, Tag>`]
+/// is assigned to, in the schedule given to [`PreviousPlugin<T, Tag>`].
+///
+/// Every [`PreviousPlugin`] instance shares this same set, so ordering
+/// against it orders against *all* of them at once. Use it to guarantee
+/// whether your own systems see the old or the freshly-updated value of a
+/// [`Previous`] component:
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_previous::*;
+/// # #[derive(Component, Clone)]
+/// # struct Health(pub u32);
+/// fn read_before_update(query: Query<(&Health, &Previous<Health>)>) {}
+///
+/// App::new()
+///     .add_plugins(PreviousPlugin::<Health>::default())
+///     .add_systems(Last, read_before_update.before(PreviousSet));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PreviousSet;
+
+// Entities without a Previous<T, Tag> yet are always snapshotted
+// immediately, regardless of `every`, so they aren't left unmatched by
+// queries like `Query<(&T, &Previous<T>)>` until the next sampled tick.
+fn init<T: Component + Clone, Tag: Send + Sync + 'static>(
     mut commands: Commands,
-    query: Query<(Entity, &T), UpdateFilter<T>>,
+    query: Query<(Entity, &T), Without<Previous<T, Tag>>>,
 ) {
     for (entity, t) in &query {
         commands
             .entity(entity)
-            .insert(Previous::<T>::new(t.clone()));
+            .insert(Previous::<T, Tag>::new(t.clone()));
     }
 }
 
-impl<T, S> PreviousPlugin<T, S>
+// Gated behind a `run_if` (rather than skipping the loop body on an
+// internal counter) so that on ticks where this doesn't run, Bevy doesn't
+// advance the system's last-run tick, and `Changed<T>` still reports any
+// change since the last time this system actually executed.
+fn sample<T: Component + Clone, Tag: Send + Sync + 'static>(
+    mut commands: Commands,
+    query: Query<(Entity, &T), Changed<T>>,
+) {
+    for (entity, t) in &query {
+        commands
+            .entity(entity)
+            .insert(Previous::<T, Tag>::new(t.clone()));
+    }
+}
+
+/// Removes the now-stale [`Previous<T, Tag>`] from entities that `T` was
+/// just removed from, so it doesn't linger with an outdated value forever.
+/// Skipped entirely when [`PreviousPlugin::keep_last_on_removal`] is set.
+fn remove_stale<T: Component + Clone, Tag: Send + Sync + 'static>(
+    mut commands: Commands,
+    mut removed: RemovedComponents<T>,
+) {
+    for entity in removed.read() {
+        commands.entity(entity).remove::<Previous<T, Tag>>();
+    }
+}
+
+impl<T, Tag> PreviousPlugin<T, Tag>
 where
     T: Component + Clone,
-    S: ScheduleLabel + Clone,
+    Tag: Send + Sync + 'static,
 {
-    pub fn new(schedule: S) -> PreviousPlugin<T, S> {
+    pub fn new(schedule: impl ScheduleLabel) -> PreviousPlugin<T, Tag> {
         PreviousPlugin {
-            schedule,
+            schedule_label: schedule.intern(),
+            keep_last_on_removal: false,
+            every: 1,
             _t: PhantomData,
+            _tag: PhantomData,
         }
     }
+
+    /// Only copy `T` into [`Previous<T, Tag>`] every `n`th execution of the
+    /// schedule, instead of every single one. Useful for expensive-to-clone
+    /// components that only need comparing every few frames, or to widen the
+    /// gap for interpolation that wants a value from further back in time.
+    ///
+    /// Entities without a [`Previous<T, Tag>`] yet (e.g. just spawned) are
+    /// always snapshotted on the very next execution regardless of `n`, so
+    /// they aren't left unmatched by queries like `Query<(&T, &Previous<T>)>`.
+    ///
+    /// Note that with `n > 1`, [`Previous<T, Tag>`] reflects `T`'s value as
+    /// of the last *sampled* tick, not necessarily the last tick where `T`
+    /// changed — so pairing this with a `Changed<T>` filter on your own
+    /// systems will observe `T` changing more often than [`Previous<T, Tag>`]
+    /// catches up.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn every(mut self, n: u32) -> Self {
+        assert!(n > 0, "PreviousPlugin::every requires n > 0");
+        self.every = n;
+        self
+    }
+
+    /// By default, when `T` is removed from an entity its [`Previous<T, Tag>`]
+    /// is removed too, in the same cycle, so stale values don't linger and
+    /// archetypes don't accumulate orphaned components.
+    ///
+    /// Calling this switches that off: [`Previous<T, Tag>`] is left on the
+    /// entity holding the last value that was copied from `T` before `T` was
+    /// removed (changes to `T` after the last sampled tick are not included),
+    /// e.g. so a death/destruction animation can still read it.
+    pub fn keep_last_on_removal(mut self) -> Self {
+        self.keep_last_on_removal = true;
+        self
+    }
 }
 
-impl<T: Component + Clone, S: ScheduleLabel + Clone> Default for PreviousPlugin<T, S>
+impl<T, Tag> Default for PreviousPlugin<T, Tag>
 where
     T: Component + Clone,
-    S: ScheduleLabel + Clone + DefaultSchedule,
+    Tag: Send + Sync + 'static,
 {
     fn default() -> Self {
-        Self::new(S::default())
+        Self::new(Last)
     }
 }
-
-/// A trait to provide the default value for a schedule label.
-///
-/// For most schedule labels, that are unit structs, `#[derive(DefaultSchedule)]`
-/// will work.
-/// For schedule labels that aren't unit structs, implementing [`DefaultSchedule`]
-/// doesn't make much sense anyways.
-///
-/// Why not just use [`Default`]? None of the bevy schedule labels actually implement
-/// [`Default`], and so this crate had to make it's own trait.
-pub trait DefaultSchedule {
-    fn default() -> Self;
-}
-
-mod default_schedule_impls {
-    use super::DefaultSchedule;
-
-    use bevy::app::*;
-
-    macro_rules! default_schedule_impls {
-        ($($schedule:ident),*) => {
-            $(
-                impl DefaultSchedule for $schedule {
-                    fn default() -> Self {
-                        $schedule
-                    }
-                }
-            )*
-        };
-    }
-
-    default_schedule_impls!(PreStartup, Startup, PostStartup);
-    default_schedule_impls!(Main, First, PreUpdate, Update, PostUpdate, Last);
-    default_schedule_impls!(
-        FixedMain,
-        FixedFirst,
-        FixedPreUpdate,
-        FixedUpdate,
-        FixedPostUpdate,
-        FixedLast
-    );
-}